@@ -0,0 +1,37 @@
+// Wall time of the all_languages binary when sampling languages sequentially and in parallel.
+// Needs a dataset, so it only runs when one is given:
+//
+//   DJANCO_DATASET=/path/to/dataset DJANCO_CACHE=/path/to/cache cargo bench --bench all_languages
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+fn run(dataset: &str, cache: &str, output: &str, extra: &[&str]) -> Duration {
+    let start = Instant::now();
+    let status = Command::new(env!("CARGO_BIN_EXE_all_languages"))
+        .args(["--dataset-path", dataset, "--cache-path", cache, "--output-path", output])
+        .args(extra)
+        .status()
+        .expect("Cannot run all_languages");
+    assert!(status.success(), "all_languages failed: {}", status);
+    start.elapsed()
+}
+
+fn main() {
+    let dataset = match std::env::var("DJANCO_DATASET") {
+        Ok(dataset) => dataset,
+        Err(_) => {
+            eprintln!("DJANCO_DATASET is not set, skipping the all_languages benchmark.");
+            return
+        }
+    };
+    let cache = std::env::var("DJANCO_CACHE").unwrap_or_else(|_| format!("{}/cache", dataset));
+    let output = std::env::temp_dir().join("all_languages_bench");
+    let output = output.to_str().unwrap();
+
+    let sequential = run(&dataset, &cache, output, &["--sequential"]);
+    let parallel = run(&dataset, &cache, output, &[]);
+
+    println!("sequential: {:>8.1}s", sequential.as_secs_f64());
+    println!("parallel:   {:>8.1}s", parallel.as_secs_f64());
+    println!("speedup:    {:>8.2}x", sequential.as_secs_f64() / parallel.as_secs_f64());
+}
@@ -0,0 +1,62 @@
+// Projects per second converted by _map_to_output_format, for HEAD trees of around 10, 100, and
+// 1000 files. Djanco cannot build projects in memory, so the projects come from a dataset and the
+// benchmark only runs when one is given:
+//
+//   DJANCO_DATASET=/path/to/dataset DJANCO_CACHE=/path/to/cache cargo bench --bench throughput
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use djanco::objects::*;
+
+use what_constitutes_software_query::{_map_to_output_format, SamplingContext};
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+// Tree sizes to measure; projects with between half and twice as many files count towards a size.
+const TREE_SIZES: [usize; 3] = [10, 100, 1000];
+// Projects per tree size, enough to average out differences between projects.
+const PROJECTS_PER_SIZE: usize = 100;
+
+fn throughput(c: &mut Criterion) {
+    if std::env::var("DJANCO_DATASET").is_err() {
+        eprintln!("DJANCO_DATASET is not set, skipping the throughput benchmark.");
+        return
+    }
+    let database = common::database();
+    let mut buckets: Vec<Vec<ItemWithData<Project>>> = TREE_SIZES.iter().map(|_| Vec::new()).collect();
+    for project in database.projects() {
+        let files = match _map_to_output_format(&project, &mut SamplingContext::new()) {
+            Some(rows) => rows.len(),
+            None => continue,
+        };
+        let bucket = TREE_SIZES.iter().position(|size| files >= size / 2 && files < size * 2);
+        if let Some(bucket) = bucket {
+            if buckets[bucket].len() < PROJECTS_PER_SIZE {
+                buckets[bucket].push(project);
+            }
+        }
+        if buckets.iter().all(|bucket| bucket.len() == PROJECTS_PER_SIZE) {
+            break
+        }
+    }
+
+    let mut group = c.benchmark_group("map_to_output_format");
+    for (size, projects) in TREE_SIZES.iter().zip(buckets.iter()) {
+        if projects.is_empty() {
+            eprintln!("No projects with around {} files in the dataset, skipping.", size);
+            continue
+        }
+        group.throughput(Throughput::Elements(projects.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(size), projects, |b, projects| {
+            b.iter(|| {
+                let mut context = SamplingContext::new();
+                for project in projects.iter() {
+                    black_box(_map_to_output_format(project, &mut context));
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, throughput);
+criterion_main!(benches);
@@ -0,0 +1,463 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Instant;
+
+use djanco::*;
+use djanco::database::*;
+use djanco::log::*;
+use djanco::utils::*;
+use clap::Parser;
+use rayon::prelude::*;
+
+use what_constitutes_software_query;
+
+const PROJECT_NAME: &str = "what_constitutes_software_query";
+
+// Subdirectories of the output path, one per language, in the order they are sampled.
+// Other languages need sample_* functions in src/lib.rs first.
+const LANGUAGES: [&str; 8] = ["java", "python", "javascript", "scala", "cpp", "typescript", "go", "csharp"];
+
+#[derive(Parser)]
+struct AllLanguagesOptions {
+    #[clap(flatten)]
+    options: what_constitutes_software_query::Options,
+
+    /// Sample one language after another instead of all languages in parallel
+    #[clap(long)]
+    sequential: bool,
+
+    /// Write all samples into the output path itself, prefixing file names with the language,
+    /// instead of into one subdirectory per language (implies --sequential)
+    #[clap(long)]
+    no_output_subdir_per_language: bool,
+}
+
+// Where a language's samples go: its own subdirectory of the output path, or the output path itself.
+fn language_output(output: &Path, language: &str, subdir_per_language: bool) -> PathBuf {
+    if subdir_per_language {
+        output.join(language)
+    } else {
+        output.to_path_buf()
+    }
+}
+
+// Without subdirectories, the languages are told apart by a language prefix added to the output
+// prefix. The settings are global, so this only works when sampling one language at a time.
+fn language_settings(settings: &what_constitutes_software_query::Settings, language: &str) -> what_constitutes_software_query::Settings {
+    let output_prefix = match settings.output_prefix.as_ref() {
+        Some(prefix) => format!("{}_{}", prefix, language),
+        None => language.to_owned(),
+    };
+    what_constitutes_software_query::Settings { output_prefix: Some(output_prefix), ..settings.clone() }
+}
+
+// Run all the queries for one language, writing into that language's output directory. Returns
+// false if it stopped early because too many projects were skipped (see --max-warnings). Languages
+// may be sampled in parallel, so exiting is left to the caller, once every language has stopped.
+fn sample_language(database: &Database, log: &Log, output: &Path, language: &str) -> bool {
+    macro_rules! execute_query {
+        ($method:path) => {
+            what_constitutes_software_query::reset_progress();
+            timed_query!($method[database, log, output]);
+            if what_constitutes_software_query::warning_limit_exceeded() {
+                eprintln!("ERROR: too many projects skipped (see --max-warnings), the last {} sample is incomplete.", language);
+                return false
+            }
+        }
+    }
+
+    match language {
+        "java" => {
+            execute_query!(what_constitutes_software_query::sample_stars_java);
+            execute_query!(what_constitutes_software_query::sample_all_java);
+            execute_query!(what_constitutes_software_query::sample_developed_java);
+            execute_query!(what_constitutes_software_query::sample_intersection_java);
+            execute_query!(what_constitutes_software_query::sample_star_weighted_java);
+            execute_query!(what_constitutes_software_query::sample_balanced_quartiles_java);
+            execute_query!(what_constitutes_software_query::sample_top_per_organization_java);
+            execute_query!(what_constitutes_software_query::sample_high_impact_java);
+            execute_query!(what_constitutes_software_query::sample_low_impact_java);
+            execute_query!(what_constitutes_software_query::sample_stateful_java);
+            execute_query!(what_constitutes_software_query::sample_internationalized_java);
+            execute_query!(what_constitutes_software_query::sample_web_aware_java);
+            execute_query!(what_constitutes_software_query::sample_stars_java_licensed);
+            execute_query!(what_constitutes_software_query::sample_with_tests_java);
+            execute_query!(what_constitutes_software_query::sample_governed_community_java);
+            execute_query!(what_constitutes_software_query::sample_community_standards_java);
+            execute_query!(what_constitutes_software_query::sample_evolved_java);
+            execute_query!(what_constitutes_software_query::sample_microservices_java);
+            execute_query!(what_constitutes_software_query::sample_low_duplication_java);
+            execute_query!(what_constitutes_software_query::sample_stratified_by_locs_java);
+            execute_query!(what_constitutes_software_query::sample_bottom_stars_java);
+            execute_query!(what_constitutes_software_query::sample_community_healthy_java);
+            execute_query!(what_constitutes_software_query::sample_young_projects_java);
+            execute_query!(what_constitutes_software_query::sample_low_star_java);
+            execute_query!(what_constitutes_software_query::sample_mid_age_java);
+            execute_query!(what_constitutes_software_query::sample_mature_java);
+            execute_query!(what_constitutes_software_query::sample_legacy_java);
+            execute_query!(what_constitutes_software_query::sample_with_legacy_code_java);
+            execute_query!(what_constitutes_software_query::sample_platform_agnostic_java);
+            execute_query!(what_constitutes_software_query::sample_platform_specific_java);
+            execute_query!(what_constitutes_software_query::sample_regular_commits_java);
+            execute_query!(what_constitutes_software_query::sample_early_adopter_led_java);
+            execute_query!(what_constitutes_software_query::sample_with_hotspot_java);
+            execute_query!(what_constitutes_software_query::sample_ci_java);
+            execute_query!(what_constitutes_software_query::sample_semantic_release_java);
+            execute_query!(what_constitutes_software_query::sample_docker_compose_java);
+            execute_query!(what_constitutes_software_query::sample_forks_of_mature_java);
+            execute_query!(what_constitutes_software_query::sample_forks_of_young_java);
+            execute_query!(what_constitutes_software_query::sample_automated_java);
+            execute_query!(what_constitutes_software_query::sample_sustained_team_java);
+            execute_query!(what_constitutes_software_query::sample_high_engagement_java);
+            execute_query!(what_constitutes_software_query::sample_clean_history_java);
+            execute_query!(what_constitutes_software_query::sample_small_team_java);
+            execute_query!(what_constitutes_software_query::sample_mid_team_java);
+            execute_query!(what_constitutes_software_query::sample_large_team_java);
+            execute_query!(what_constitutes_software_query::sample_container_tests_java);
+            execute_query!(what_constitutes_software_query::sample_fully_automated_java);
+            execute_query!(what_constitutes_software_query::sample_financially_sustainable_java);
+            execute_query!(what_constitutes_software_query::sample_peaked_java);
+            execute_query!(what_constitutes_software_query::sample_with_obsolete_code_java);
+            execute_query!(what_constitutes_software_query::sample_medium_size_java);
+            execute_query!(what_constitutes_software_query::sample_large_files_java);
+            execute_query!(what_constitutes_software_query::sample_small_files_java);
+            execute_query!(what_constitutes_software_query::sample_with_multiple_branches_java);
+            execute_query!(what_constitutes_software_query::sample_hand_written_java);
+            execute_query!(what_constitutes_software_query::sample_history_java);
+            execute_query!(what_constitutes_software_query::sample_stable_java);
+            execute_query!(what_constitutes_software_query::sample_container_aware_java);
+            execute_query!(what_constitutes_software_query::sample_monitored_java);
+            execute_query!(what_constitutes_software_query::sample_issue_tracked_commits_java);
+            execute_query!(what_constitutes_software_query::sample_low_test_debt_java);
+            execute_query!(what_constitutes_software_query::sample_matrix_ci_java);
+            execute_query!(what_constitutes_software_query::sample_code_repositories_java);
+            execute_query!(what_constitutes_software_query::sample_copyrighted_java);
+            execute_query!(what_constitutes_software_query::sample_linted_java);
+            execute_query!(what_constitutes_software_query::sample_scoped_java);
+            execute_query!(what_constitutes_software_query::sample_no_vendored_java);
+            execute_query!(what_constitutes_software_query::sample_maven_java);
+            execute_query!(what_constitutes_software_query::sample_gradle_java);
+            execute_query!(what_constitutes_software_query::sample_documented_java);
+            execute_query!(what_constitutes_software_query::sample_small_java);
+            execute_query!(what_constitutes_software_query::sample_stars_java_with_diagnostics);
+        }
+        "python" => {
+            execute_query!(what_constitutes_software_query::sample_stars_py);
+            execute_query!(what_constitutes_software_query::sample_all_py);
+            execute_query!(what_constitutes_software_query::sample_developed_py);
+            execute_query!(what_constitutes_software_query::sample_stateful_py);
+            execute_query!(what_constitutes_software_query::sample_internationalized_py);
+            execute_query!(what_constitutes_software_query::sample_web_aware_py);
+            execute_query!(what_constitutes_software_query::sample_stars_py_licensed);
+            execute_query!(what_constitutes_software_query::sample_with_tests_py);
+            execute_query!(what_constitutes_software_query::sample_governed_community_py);
+            execute_query!(what_constitutes_software_query::sample_community_standards_py);
+            execute_query!(what_constitutes_software_query::sample_evolved_py);
+            execute_query!(what_constitutes_software_query::sample_microservices_py);
+            execute_query!(what_constitutes_software_query::sample_low_duplication_py);
+            execute_query!(what_constitutes_software_query::sample_stratified_by_locs_py);
+            execute_query!(what_constitutes_software_query::sample_bottom_stars_py);
+            execute_query!(what_constitutes_software_query::sample_community_healthy_py);
+            execute_query!(what_constitutes_software_query::sample_with_legacy_code_py);
+            execute_query!(what_constitutes_software_query::sample_platform_agnostic_py);
+            execute_query!(what_constitutes_software_query::sample_platform_specific_py);
+            execute_query!(what_constitutes_software_query::sample_early_adopter_led_py);
+            execute_query!(what_constitutes_software_query::sample_with_hotspot_py);
+            execute_query!(what_constitutes_software_query::sample_ci_py);
+            execute_query!(what_constitutes_software_query::sample_semantic_release_py);
+            execute_query!(what_constitutes_software_query::sample_docker_compose_py);
+            execute_query!(what_constitutes_software_query::sample_forks_of_mature_py);
+            execute_query!(what_constitutes_software_query::sample_forks_of_young_py);
+            execute_query!(what_constitutes_software_query::sample_automated_py);
+            execute_query!(what_constitutes_software_query::sample_sustained_team_py);
+            execute_query!(what_constitutes_software_query::sample_high_engagement_py);
+            execute_query!(what_constitutes_software_query::sample_clean_history_py);
+            execute_query!(what_constitutes_software_query::sample_container_tests_py);
+            execute_query!(what_constitutes_software_query::sample_fully_automated_py);
+            execute_query!(what_constitutes_software_query::sample_financially_sustainable_py);
+            execute_query!(what_constitutes_software_query::sample_peaked_py);
+            execute_query!(what_constitutes_software_query::sample_with_obsolete_code_py);
+            execute_query!(what_constitutes_software_query::sample_medium_size_py);
+            execute_query!(what_constitutes_software_query::sample_large_files_py);
+            execute_query!(what_constitutes_software_query::sample_small_files_py);
+            execute_query!(what_constitutes_software_query::sample_with_multiple_branches_py);
+            execute_query!(what_constitutes_software_query::sample_hand_written_py);
+            execute_query!(what_constitutes_software_query::sample_stable_py);
+            execute_query!(what_constitutes_software_query::sample_container_aware_py);
+            execute_query!(what_constitutes_software_query::sample_monitored_py);
+            execute_query!(what_constitutes_software_query::sample_issue_tracked_commits_py);
+            execute_query!(what_constitutes_software_query::sample_low_test_debt_py);
+            execute_query!(what_constitutes_software_query::sample_matrix_ci_py);
+            execute_query!(what_constitutes_software_query::sample_code_repositories_py);
+            execute_query!(what_constitutes_software_query::sample_copyrighted_py);
+            execute_query!(what_constitutes_software_query::sample_linted_py);
+            execute_query!(what_constitutes_software_query::sample_scoped_py);
+            execute_query!(what_constitutes_software_query::sample_pip_py);
+            execute_query!(what_constitutes_software_query::sample_documented_py);
+            execute_query!(what_constitutes_software_query::sample_small_py);
+        }
+        "javascript" => {
+            execute_query!(what_constitutes_software_query::sample_stars_js);
+            execute_query!(what_constitutes_software_query::sample_all_js);
+            execute_query!(what_constitutes_software_query::sample_developed_js);
+            execute_query!(what_constitutes_software_query::sample_stateful_js);
+            execute_query!(what_constitutes_software_query::sample_internationalized_js);
+            execute_query!(what_constitutes_software_query::sample_web_aware_js);
+            execute_query!(what_constitutes_software_query::sample_stars_js_licensed);
+            execute_query!(what_constitutes_software_query::sample_with_tests_js);
+            execute_query!(what_constitutes_software_query::sample_governed_community_js);
+            execute_query!(what_constitutes_software_query::sample_community_standards_js);
+            execute_query!(what_constitutes_software_query::sample_evolved_js);
+            execute_query!(what_constitutes_software_query::sample_microservices_js);
+            execute_query!(what_constitutes_software_query::sample_low_duplication_js);
+            execute_query!(what_constitutes_software_query::sample_stratified_by_locs_js);
+            execute_query!(what_constitutes_software_query::sample_bottom_stars_js);
+            execute_query!(what_constitutes_software_query::sample_community_healthy_js);
+            execute_query!(what_constitutes_software_query::sample_with_legacy_code_js);
+            execute_query!(what_constitutes_software_query::sample_platform_agnostic_js);
+            execute_query!(what_constitutes_software_query::sample_platform_specific_js);
+            execute_query!(what_constitutes_software_query::sample_early_adopter_led_js);
+            execute_query!(what_constitutes_software_query::sample_with_hotspot_js);
+            execute_query!(what_constitutes_software_query::sample_ci_js);
+            execute_query!(what_constitutes_software_query::sample_semantic_release_js);
+            execute_query!(what_constitutes_software_query::sample_docker_compose_js);
+            execute_query!(what_constitutes_software_query::sample_forks_of_mature_js);
+            execute_query!(what_constitutes_software_query::sample_forks_of_young_js);
+            execute_query!(what_constitutes_software_query::sample_automated_js);
+            execute_query!(what_constitutes_software_query::sample_sustained_team_js);
+            execute_query!(what_constitutes_software_query::sample_high_engagement_js);
+            execute_query!(what_constitutes_software_query::sample_clean_history_js);
+            execute_query!(what_constitutes_software_query::sample_container_tests_js);
+            execute_query!(what_constitutes_software_query::sample_fully_automated_js);
+            execute_query!(what_constitutes_software_query::sample_financially_sustainable_js);
+            execute_query!(what_constitutes_software_query::sample_peaked_js);
+            execute_query!(what_constitutes_software_query::sample_medium_size_js);
+            execute_query!(what_constitutes_software_query::sample_large_files_js);
+            execute_query!(what_constitutes_software_query::sample_small_files_js);
+            execute_query!(what_constitutes_software_query::sample_with_multiple_branches_js);
+            execute_query!(what_constitutes_software_query::sample_hand_written_js);
+            execute_query!(what_constitutes_software_query::sample_stable_js);
+            execute_query!(what_constitutes_software_query::sample_container_aware_js);
+            execute_query!(what_constitutes_software_query::sample_monitored_js);
+            execute_query!(what_constitutes_software_query::sample_issue_tracked_commits_js);
+            execute_query!(what_constitutes_software_query::sample_low_test_debt_js);
+            execute_query!(what_constitutes_software_query::sample_matrix_ci_js);
+            execute_query!(what_constitutes_software_query::sample_code_repositories_js);
+            execute_query!(what_constitutes_software_query::sample_copyrighted_js);
+            execute_query!(what_constitutes_software_query::sample_linted_js);
+            execute_query!(what_constitutes_software_query::sample_scoped_js);
+            execute_query!(what_constitutes_software_query::sample_npm_js);
+            execute_query!(what_constitutes_software_query::sample_documented_js);
+            execute_query!(what_constitutes_software_query::sample_small_js);
+        }
+        "scala" => {
+            execute_query!(what_constitutes_software_query::sample_stars_scala);
+            execute_query!(what_constitutes_software_query::sample_all_scala);
+            execute_query!(what_constitutes_software_query::sample_developed_scala);
+            execute_query!(what_constitutes_software_query::sample_stateful_scala);
+            execute_query!(what_constitutes_software_query::sample_internationalized_scala);
+            execute_query!(what_constitutes_software_query::sample_web_aware_scala);
+            execute_query!(what_constitutes_software_query::sample_stars_scala_licensed);
+            execute_query!(what_constitutes_software_query::sample_with_tests_scala);
+            execute_query!(what_constitutes_software_query::sample_governed_community_scala);
+            execute_query!(what_constitutes_software_query::sample_community_standards_scala);
+            execute_query!(what_constitutes_software_query::sample_evolved_scala);
+            execute_query!(what_constitutes_software_query::sample_microservices_scala);
+            execute_query!(what_constitutes_software_query::sample_low_duplication_scala);
+            execute_query!(what_constitutes_software_query::sample_stratified_by_locs_scala);
+            execute_query!(what_constitutes_software_query::sample_bottom_stars_scala);
+            execute_query!(what_constitutes_software_query::sample_community_healthy_scala);
+            execute_query!(what_constitutes_software_query::sample_with_legacy_code_scala);
+            execute_query!(what_constitutes_software_query::sample_platform_agnostic_scala);
+            execute_query!(what_constitutes_software_query::sample_platform_specific_scala);
+            execute_query!(what_constitutes_software_query::sample_early_adopter_led_scala);
+            execute_query!(what_constitutes_software_query::sample_with_hotspot_scala);
+            execute_query!(what_constitutes_software_query::sample_ci_scala);
+            execute_query!(what_constitutes_software_query::sample_semantic_release_scala);
+            execute_query!(what_constitutes_software_query::sample_docker_compose_scala);
+            execute_query!(what_constitutes_software_query::sample_forks_of_mature_scala);
+            execute_query!(what_constitutes_software_query::sample_forks_of_young_scala);
+            execute_query!(what_constitutes_software_query::sample_automated_scala);
+            execute_query!(what_constitutes_software_query::sample_sustained_team_scala);
+            execute_query!(what_constitutes_software_query::sample_high_engagement_scala);
+            execute_query!(what_constitutes_software_query::sample_clean_history_scala);
+            execute_query!(what_constitutes_software_query::sample_container_tests_scala);
+            execute_query!(what_constitutes_software_query::sample_fully_automated_scala);
+            execute_query!(what_constitutes_software_query::sample_financially_sustainable_scala);
+            execute_query!(what_constitutes_software_query::sample_peaked_scala);
+            execute_query!(what_constitutes_software_query::sample_medium_size_scala);
+            execute_query!(what_constitutes_software_query::sample_large_files_scala);
+            execute_query!(what_constitutes_software_query::sample_small_files_scala);
+            execute_query!(what_constitutes_software_query::sample_with_multiple_branches_scala);
+            execute_query!(what_constitutes_software_query::sample_hand_written_scala);
+            execute_query!(what_constitutes_software_query::sample_stable_scala);
+            execute_query!(what_constitutes_software_query::sample_container_aware_scala);
+            execute_query!(what_constitutes_software_query::sample_monitored_scala);
+            execute_query!(what_constitutes_software_query::sample_issue_tracked_commits_scala);
+            execute_query!(what_constitutes_software_query::sample_low_test_debt_scala);
+            execute_query!(what_constitutes_software_query::sample_matrix_ci_scala);
+            execute_query!(what_constitutes_software_query::sample_code_repositories_scala);
+            execute_query!(what_constitutes_software_query::sample_copyrighted_scala);
+            execute_query!(what_constitutes_software_query::sample_linted_scala);
+            execute_query!(what_constitutes_software_query::sample_scoped_scala);
+        }
+        "cpp" => {
+            execute_query!(what_constitutes_software_query::sample_stars_cpp);
+            execute_query!(what_constitutes_software_query::sample_all_cpp);
+            execute_query!(what_constitutes_software_query::sample_developed_cpp);
+        }
+        "typescript" => {
+            execute_query!(what_constitutes_software_query::sample_stars_ts);
+            execute_query!(what_constitutes_software_query::sample_all_ts);
+            execute_query!(what_constitutes_software_query::sample_developed_ts);
+        }
+        "go" => {
+            execute_query!(what_constitutes_software_query::sample_stars_go);
+            execute_query!(what_constitutes_software_query::sample_all_go);
+            execute_query!(what_constitutes_software_query::sample_developed_go);
+        }
+        "csharp" => {
+            execute_query!(what_constitutes_software_query::sample_stars_csharp);
+            execute_query!(what_constitutes_software_query::sample_all_csharp);
+            execute_query!(what_constitutes_software_query::sample_developed_csharp);
+        }
+        _ => unreachable!("no queries for language {}", language),
+    }
+    true
+}
+
+fn print_summary(output: &Path, subdir_per_language: bool) {
+    println!("{:<12} {:<36} {:>8}", "language", "sample", "projects");
+    for language in LANGUAGES.iter() {
+        let directory = language_output(output, language, subdir_per_language);
+        let prefix = if subdir_per_language {
+            String::new()
+        } else {
+            what_constitutes_software_query::output_file_name(&format!("{}_", language))
+        };
+        let mut samples: Vec<_> = match std::fs::read_dir(&directory) {
+            Ok(entries) => entries.flat_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.extension() == Some("csv".as_ref()))
+                .filter(|path| !what_constitutes_software_query::output::stats::is_extension_stats(path))
+                .filter(|path| path.file_name().unwrap().to_string_lossy().starts_with(&prefix))
+                .collect(),
+            Err(error) => {
+                eprintln!("WARNING: cannot read output directory {}: {}", directory.display(), error);
+                continue
+            }
+        };
+        samples.sort();
+        for sample in samples {
+            let name = sample.file_stem().unwrap().to_string_lossy().to_string();
+            match what_constitutes_software_query::count_sampled_projects(&sample) {
+                Ok(projects) => println!("{:<12} {:<36} {:>8}", language, name, projects),
+                Err(error) => eprintln!("WARNING: cannot read sample {}: {}", sample.display(), error),
+            }
+        }
+    }
+}
+
+pub fn main() {
+
+    let AllLanguagesOptions { options, sequential, no_output_subdir_per_language } = AllLanguagesOptions::parse();
+    let settings = options.settings().expect("Invalid options");
+    what_constitutes_software_query::configure(settings.clone());
+    let subdir_per_language = !no_output_subdir_per_language;
+    let sequential = sequential || !subdir_per_language;
+    what_constitutes_software_query::set_progress_callback(Box::new(what_constitutes_software_query::print_progress));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
+
+    let repository = if let Some(repository) = options.djanco.repository.as_ref() {
+        Some(create_project_archive(PROJECT_NAME, repository.as_str()))
+    } else {
+        None
+    };
+
+    macro_rules! prepare_database {
+        ($savepoint:expr, $stores:expr) => {
+            Djanco::from_spec(dataset, cache, $savepoint, $stores, log.clone())
+                .expect("Error initializing Djanco!")
+        }
+    }
+
+    // A configuration file replaces the built-in queries of every language.
+    let directories: Vec<PathBuf> = if let Some(config) = options.config.as_ref() {
+        let database = prepare_database!(options.savepoint, stores!(Generic));
+        what_constitutes_software_query::config::run_config(&database, &log, &options.djanco.output_path, config)
+            .expect("Error running the configured queries")
+    } else {
+        // Each language writes into its own directory, so the only shared output is the list of timings.
+        let timings = Mutex::new(Vec::new());
+        let mut complete = true;
+        let start = Instant::now();
+        if sequential {
+            let database = prepare_database!(options.savepoint, stores!(Generic));
+            for language in LANGUAGES.iter() {
+                let language_start = Instant::now();
+                if !subdir_per_language {
+                    what_constitutes_software_query::configure(language_settings(&settings, language));
+                }
+                complete = sample_language(&database, &log, &language_output(&options.djanco.output_path, language, subdir_per_language), language);
+                timings.lock().unwrap().push((language, language_start.elapsed()));
+                if !complete {
+                    break
+                }
+            }
+            what_constitutes_software_query::configure(settings);
+        } else {
+            // The warnings are counted for the whole run, so once there are too many, every language
+            // stops after its current query.
+            let completed: Vec<bool> = LANGUAGES.par_iter().map(|language| {
+                // Djanco databases are not shared between threads, so each language gets its own.
+                let language_start = Instant::now();
+                let database = prepare_database!(options.savepoint, stores!(Generic));
+                let complete = sample_language(&database, &log, &language_output(&options.djanco.output_path, language, subdir_per_language), language);
+                timings.lock().unwrap().push((language, language_start.elapsed()));
+                complete
+            }).collect();
+            complete = completed.into_iter().all(|complete| complete);
+        }
+        for (language, elapsed) in timings.into_inner().unwrap() {
+            eprintln!("Sampled {} in {}s", language, elapsed.as_secs());
+        }
+        eprintln!("Sampled all languages ({}) in {}s", if sequential { "sequentially" } else { "in parallel" }, start.elapsed().as_secs());
+        if !complete {
+            what_constitutes_software_query::print_warning_summary();
+            std::process::exit(1);
+        }
+
+        if subdir_per_language {
+            LANGUAGES.iter().map(|language| options.djanco.output_path.join(language)).collect()
+        } else {
+            vec![options.djanco.output_path.clone()]
+        }
+    };
+    for directory in directories.iter() {
+        what_constitutes_software_query::output::export_samples(directory, options.output_format)
+            .expect("Error exporting samples");
+    }
+    what_constitutes_software_query::print_warning_summary();
+    if options.config.is_none() {
+        print_summary(&options.djanco.output_path, subdir_per_language);
+    }
+    if options.include_url {
+        let database = prepare_database!(options.savepoint, stores!(Generic));
+        for directory in directories.iter() {
+            what_constitutes_software_query::output::write_url_files(&database, directory)
+                .expect("Error writing project URLs");
+        }
+    }
+    if options.compress {
+        for directory in directories.iter() {
+            what_constitutes_software_query::output::compress_samples(directory)
+                .expect("Error compressing samples");
+        }
+    }
+
+    if options.djanco.repository.is_some() && !options.djanco.do_not_archive_results {
+        add_results(PROJECT_NAME, &repository.unwrap(), &options.djanco.output_path, options.djanco.size_limit);
+    }
+}
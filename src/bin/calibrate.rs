@@ -0,0 +1,38 @@
+use djanco::*;
+use djanco::log::*;
+use clap::Parser;
+
+use what_constitutes_software_query;
+
+// Languages to calibrate, by the names used with --language-override.
+const LANGUAGES: [&str; 8] = ["java", "python", "javascript", "scala", "cpp", "typescript", "go", "csharp"];
+
+#[derive(Parser)]
+struct CalibrateOptions {
+    #[clap(flatten)]
+    options: what_constitutes_software_query::Options,
+
+    /// Percentile (0 to 100) of the projects to take the sample_developed_* thresholds at
+    #[clap(long, default_value = "50")]
+    percentile: f64,
+}
+
+// Print the thresholds of the sample_developed_* queries for each language, as the comments above
+// them, for copy-paste into new sample functions.
+pub fn main() {
+
+    let CalibrateOptions { options, percentile } = CalibrateOptions::parse();
+    what_constitutes_software_query::configure(options.settings().expect("Invalid options"));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
+
+    let database = Djanco::from_spec(dataset, cache, options.savepoint, stores!(Generic), log.clone())
+        .expect("Error initializing Djanco!");
+    for name in LANGUAGES.iter() {
+        let language = what_constitutes_software_query::language_from_name(name).unwrap();
+        let thresholds = what_constitutes_software_query::calibrate_thresholds(&database, language, percentile);
+        println!("// {} at the {}th percentile", name, percentile);
+        println!("{}", thresholds.to_comment());
+    }
+}
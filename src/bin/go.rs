@@ -0,0 +1,74 @@
+use djanco::*;
+use djanco::log::*;
+use djanco::utils::*;
+use clap::Parser;
+
+use what_constitutes_software_query;
+
+const PROJECT_NAME: &str = "what_constitutes_software_query";
+
+pub fn main() {
+
+    let options = what_constitutes_software_query::Options::parse();
+    what_constitutes_software_query::configure(options.settings().expect("Invalid options"));
+    what_constitutes_software_query::set_progress_callback(Box::new(what_constitutes_software_query::print_progress));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
+
+    let repository = if let Some(repository) = options.djanco.repository.as_ref() {
+        Some(create_project_archive(PROJECT_NAME, repository.as_str()))
+    } else {
+        None
+    };
+
+    macro_rules! execute_query {
+        ($database:expr, $method:path) => {
+            what_constitutes_software_query::reset_progress();
+            timed_query!($method[&$database, &log, &options.djanco.output_path]);
+            if what_constitutes_software_query::warning_limit_exceeded() {
+                what_constitutes_software_query::print_warning_summary();
+                eprintln!("ERROR: too many projects skipped (see --max-warnings), the last sample is incomplete.");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    macro_rules! prepare_database {
+        ($savepoint:expr, $stores:expr) => {
+            Djanco::from_spec(dataset, cache, $savepoint, $stores, log.clone())
+                .expect("Error initializing Djanco!")
+        }
+    }
+
+    let database = prepare_database!(options.savepoint, stores!(Generic));
+    let directories = match options.config.as_ref() {
+        Some(config) => what_constitutes_software_query::config::run_config(&database, &log, &options.djanco.output_path, config)
+            .expect("Error running the configured queries"),
+        None => {
+            execute_query!(database, what_constitutes_software_query::sample_stars_go);
+            execute_query!(database, what_constitutes_software_query::sample_all_go);
+            execute_query!(database, what_constitutes_software_query::sample_developed_go);
+            vec![options.djanco.output_path.clone()]
+        }
+    };
+
+
+    for directory in directories.iter() {
+        what_constitutes_software_query::output::export_samples(directory, options.output_format)
+            .expect("Error exporting samples");
+        if options.include_url {
+            what_constitutes_software_query::output::write_url_files(&database, directory)
+                .expect("Error writing project URLs");
+        }
+        if options.compress {
+            what_constitutes_software_query::output::compress_samples(directory)
+                .expect("Error compressing samples");
+        }
+    }
+    what_constitutes_software_query::print_warning_summary();
+
+    if options.djanco.repository.is_some() && !options.djanco.do_not_archive_results {
+        add_results(PROJECT_NAME, &repository.unwrap(), &options.djanco.output_path, options.djanco.size_limit);
+    }
+}
@@ -9,12 +9,14 @@ const PROJECT_NAME: &'static str = "what_constitutes_software_query";
 
 pub fn main() {
 
-    let options = CommandLineOptions::parse();
-    let log = Log::new(options.verbosity);
-    let dataset = options.dataset_path_as_str();
-    let cache = options.cache_path_as_str();
+    let options = what_constitutes_software_query::Options::parse();
+    what_constitutes_software_query::configure(options.settings().expect("Invalid options"));
+    what_constitutes_software_query::set_progress_callback(Box::new(what_constitutes_software_query::print_progress));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
 
-    let repository = if let Some(repository) = options.repository.as_ref() {
+    let repository = if let Some(repository) = options.djanco.repository.as_ref() {
         Some(create_project_archive(PROJECT_NAME, repository.as_str()))
     } else {
         None
@@ -22,24 +24,120 @@ pub fn main() {
 
     macro_rules! execute_query {
         ($database:expr, $method:path) => {
-            timed_query!($method[&$database, &log, &options.output_path]);
+            what_constitutes_software_query::reset_progress();
+            timed_query!($method[&$database, &log, &options.djanco.output_path]);
+            if what_constitutes_software_query::warning_limit_exceeded() {
+                what_constitutes_software_query::print_warning_summary();
+                eprintln!("ERROR: too many projects skipped (see --max-warnings), the last sample is incomplete.");
+                std::process::exit(1);
+            }
         }
     }
 
     macro_rules! prepare_database {
         ($savepoint:expr, $stores:expr) => {
             Djanco::from_spec(dataset, cache, $savepoint, $stores, log.clone())
-                .expect("Error initializing Djanco!");
+                .expect("Error initializing Djanco!")
         }
     }
 
-    let database = prepare_database!(1606780800 /* = December 2020*/, stores!(Generic));
-    execute_query!(database, what_constitutes_software_query::sample_stars_java);
-    execute_query!(database, what_constitutes_software_query::sample_all_java);
-    execute_query!(database, what_constitutes_software_query::sample_developed_java);
+    let database = prepare_database!(options.savepoint, stores!(Generic));
+    let directories = match options.config.as_ref() {
+        Some(config) => what_constitutes_software_query::config::run_config(&database, &log, &options.djanco.output_path, config)
+            .expect("Error running the configured queries"),
+        None => {
+            execute_query!(database, what_constitutes_software_query::sample_stars_java);
+            execute_query!(database, what_constitutes_software_query::sample_all_java);
+            execute_query!(database, what_constitutes_software_query::sample_developed_java);
+            execute_query!(database, what_constitutes_software_query::sample_intersection_java);
+            execute_query!(database, what_constitutes_software_query::sample_star_weighted_java);
+            execute_query!(database, what_constitutes_software_query::sample_balanced_quartiles_java);
+            execute_query!(database, what_constitutes_software_query::sample_top_per_organization_java);
+            execute_query!(database, what_constitutes_software_query::sample_high_impact_java);
+            execute_query!(database, what_constitutes_software_query::sample_low_impact_java);
+            execute_query!(database, what_constitutes_software_query::sample_stateful_java);
+            execute_query!(database, what_constitutes_software_query::sample_internationalized_java);
+            execute_query!(database, what_constitutes_software_query::sample_web_aware_java);
+            execute_query!(database, what_constitutes_software_query::sample_stars_java_licensed);
+            execute_query!(database, what_constitutes_software_query::sample_with_tests_java);
+            execute_query!(database, what_constitutes_software_query::sample_governed_community_java);
+            execute_query!(database, what_constitutes_software_query::sample_community_standards_java);
+            execute_query!(database, what_constitutes_software_query::sample_evolved_java);
+            execute_query!(database, what_constitutes_software_query::sample_microservices_java);
+            execute_query!(database, what_constitutes_software_query::sample_low_duplication_java);
+            execute_query!(database, what_constitutes_software_query::sample_stratified_by_locs_java);
+            execute_query!(database, what_constitutes_software_query::sample_bottom_stars_java);
+            execute_query!(database, what_constitutes_software_query::sample_community_healthy_java);
+            execute_query!(database, what_constitutes_software_query::sample_young_projects_java);
+            execute_query!(database, what_constitutes_software_query::sample_low_star_java);
+            execute_query!(database, what_constitutes_software_query::sample_mid_age_java);
+            execute_query!(database, what_constitutes_software_query::sample_mature_java);
+            execute_query!(database, what_constitutes_software_query::sample_legacy_java);
+            execute_query!(database, what_constitutes_software_query::sample_with_legacy_code_java);
+            execute_query!(database, what_constitutes_software_query::sample_platform_agnostic_java);
+            execute_query!(database, what_constitutes_software_query::sample_platform_specific_java);
+            execute_query!(database, what_constitutes_software_query::sample_regular_commits_java);
+            execute_query!(database, what_constitutes_software_query::sample_early_adopter_led_java);
+            execute_query!(database, what_constitutes_software_query::sample_with_hotspot_java);
+            execute_query!(database, what_constitutes_software_query::sample_ci_java);
+            execute_query!(database, what_constitutes_software_query::sample_semantic_release_java);
+            execute_query!(database, what_constitutes_software_query::sample_docker_compose_java);
+            execute_query!(database, what_constitutes_software_query::sample_forks_of_mature_java);
+            execute_query!(database, what_constitutes_software_query::sample_forks_of_young_java);
+            execute_query!(database, what_constitutes_software_query::sample_automated_java);
+            execute_query!(database, what_constitutes_software_query::sample_sustained_team_java);
+            execute_query!(database, what_constitutes_software_query::sample_high_engagement_java);
+            execute_query!(database, what_constitutes_software_query::sample_stars_java_with_diagnostics);
+            execute_query!(database, what_constitutes_software_query::sample_clean_history_java);
+            execute_query!(database, what_constitutes_software_query::sample_small_team_java);
+            execute_query!(database, what_constitutes_software_query::sample_mid_team_java);
+            execute_query!(database, what_constitutes_software_query::sample_large_team_java);
+            execute_query!(database, what_constitutes_software_query::sample_container_tests_java);
+            execute_query!(database, what_constitutes_software_query::sample_fully_automated_java);
+            execute_query!(database, what_constitutes_software_query::sample_financially_sustainable_java);
+            execute_query!(database, what_constitutes_software_query::sample_peaked_java);
+            execute_query!(database, what_constitutes_software_query::sample_with_obsolete_code_java);
+            execute_query!(database, what_constitutes_software_query::sample_medium_size_java);
+            execute_query!(database, what_constitutes_software_query::sample_large_files_java);
+            execute_query!(database, what_constitutes_software_query::sample_small_files_java);
+            execute_query!(database, what_constitutes_software_query::sample_with_multiple_branches_java);
+            execute_query!(database, what_constitutes_software_query::sample_hand_written_java);
+            execute_query!(database, what_constitutes_software_query::sample_history_java);
+            execute_query!(database, what_constitutes_software_query::sample_stable_java);
+            execute_query!(database, what_constitutes_software_query::sample_container_aware_java);
+            execute_query!(database, what_constitutes_software_query::sample_monitored_java);
+            execute_query!(database, what_constitutes_software_query::sample_issue_tracked_commits_java);
+            execute_query!(database, what_constitutes_software_query::sample_low_test_debt_java);
+            execute_query!(database, what_constitutes_software_query::sample_matrix_ci_java);
+            execute_query!(database, what_constitutes_software_query::sample_code_repositories_java);
+            execute_query!(database, what_constitutes_software_query::sample_copyrighted_java);
+            execute_query!(database, what_constitutes_software_query::sample_linted_java);
+            execute_query!(database, what_constitutes_software_query::sample_scoped_java);
+            execute_query!(database, what_constitutes_software_query::sample_no_vendored_java);
+            execute_query!(database, what_constitutes_software_query::sample_maven_java);
+            execute_query!(database, what_constitutes_software_query::sample_gradle_java);
+            execute_query!(database, what_constitutes_software_query::sample_documented_java);
+            execute_query!(database, what_constitutes_software_query::sample_small_java);
+            vec![options.djanco.output_path.clone()]
+        }
+    };
+
 
+    for directory in directories.iter() {
+        what_constitutes_software_query::output::export_samples(directory, options.output_format)
+            .expect("Error exporting samples");
+        if options.include_url {
+            what_constitutes_software_query::output::write_url_files(&database, directory)
+                .expect("Error writing project URLs");
+        }
+        if options.compress {
+            what_constitutes_software_query::output::compress_samples(directory)
+                .expect("Error compressing samples");
+        }
+    }
+    what_constitutes_software_query::print_warning_summary();
 
-    if options.repository.is_some() && !options.do_not_archive_results {
-        add_results(PROJECT_NAME, &repository.unwrap(), &options.output_path, options.size_limit);
+    if options.djanco.repository.is_some() && !options.djanco.do_not_archive_results {
+        add_results(PROJECT_NAME, &repository.unwrap(), &options.djanco.output_path, options.djanco.size_limit);
     }
 }
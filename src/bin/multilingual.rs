@@ -0,0 +1,86 @@
+use std::time::Instant;
+
+use djanco::*;
+use djanco::log::*;
+use djanco::objects::*;
+use djanco::utils::*;
+use clap::Parser;
+
+use what_constitutes_software_query;
+
+const PROJECT_NAME: &str = "what_constitutes_software_query";
+
+pub fn main() {
+
+    let options = what_constitutes_software_query::Options::parse();
+    what_constitutes_software_query::configure(options.settings().expect("Invalid options"));
+    what_constitutes_software_query::set_progress_callback(Box::new(what_constitutes_software_query::print_progress));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
+
+    let repository = if let Some(repository) = options.djanco.repository.as_ref() {
+        Some(create_project_archive(PROJECT_NAME, repository.as_str()))
+    } else {
+        None
+    };
+
+    // sample_multilingual takes the pair of languages, so it cannot go through timed_query!.
+    macro_rules! execute_multilingual_query {
+        ($database:expr, $primary:expr, $secondary:expr) => {
+            what_constitutes_software_query::reset_progress();
+            let start = Instant::now();
+            let result = what_constitutes_software_query::sample_multilingual(
+                &$database, &log, &options.djanco.output_path, $primary, $secondary, what_constitutes_software_query::SELECTION_SIZE
+            );
+            match result {
+                Ok(()) => eprintln!("Sampled {} with {} in {}s", $primary, $secondary, start.elapsed().as_secs()),
+                Err(error) => eprintln!("ERROR: sampling {} with {} failed: {}", $primary, $secondary, error),
+            }
+            if what_constitutes_software_query::warning_limit_exceeded() {
+                what_constitutes_software_query::print_warning_summary();
+                eprintln!("ERROR: too many projects skipped (see --max-warnings), the last sample is incomplete.");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    macro_rules! prepare_database {
+        ($savepoint:expr, $stores:expr) => {
+            Djanco::from_spec(dataset, cache, $savepoint, $stores, log.clone())
+                .expect("Error initializing Djanco!")
+        }
+    }
+
+    let database = prepare_database!(options.savepoint, stores!(Generic));
+    let directories = match options.config.as_ref() {
+        Some(config) => what_constitutes_software_query::config::run_config(&database, &log, &options.djanco.output_path, config)
+            .expect("Error running the configured queries"),
+        None => {
+            execute_multilingual_query!(database, Language::Java, Language::Scala);
+            execute_multilingual_query!(database, Language::Java, Language::JavaScript);
+            execute_multilingual_query!(database, Language::Python, Language::JavaScript);
+            execute_multilingual_query!(database, Language::JavaScript, Language::Python);
+            execute_multilingual_query!(database, Language::Scala, Language::Java);
+            vec![options.djanco.output_path.clone()]
+        }
+    };
+
+    for directory in directories.iter() {
+        what_constitutes_software_query::output::export_samples(directory, options.output_format)
+            .expect("Error exporting samples");
+        if options.include_url {
+            what_constitutes_software_query::output::write_url_files(&database, directory)
+                .expect("Error writing project URLs");
+        }
+        if options.compress {
+            what_constitutes_software_query::output::compress_samples(directory)
+                .expect("Error compressing samples");
+        }
+    }
+    what_constitutes_software_query::print_warning_summary();
+
+    if options.djanco.repository.is_some() && !options.djanco.do_not_archive_results {
+        add_results(PROJECT_NAME, &repository.unwrap(), &options.djanco.output_path, options.djanco.size_limit);
+    }
+}
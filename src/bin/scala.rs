@@ -0,0 +1,120 @@
+use djanco::*;
+use djanco::log::*;
+use djanco::utils::*;
+use clap::Parser;
+
+use what_constitutes_software_query;
+
+const PROJECT_NAME: &str = "what_constitutes_software_query";
+
+pub fn main() {
+
+    let options = what_constitutes_software_query::Options::parse();
+    what_constitutes_software_query::configure(options.settings().expect("Invalid options"));
+    what_constitutes_software_query::set_progress_callback(Box::new(what_constitutes_software_query::print_progress));
+    let log = Log::new(options.djanco.verbosity);
+    let dataset = options.djanco.dataset_path_as_str();
+    let cache = options.djanco.cache_path_as_str();
+
+    let repository = if let Some(repository) = options.djanco.repository.as_ref() {
+        Some(create_project_archive(PROJECT_NAME, repository.as_str()))
+    } else {
+        None
+    };
+
+    macro_rules! execute_query {
+        ($database:expr, $method:path) => {
+            what_constitutes_software_query::reset_progress();
+            timed_query!($method[&$database, &log, &options.djanco.output_path]);
+            if what_constitutes_software_query::warning_limit_exceeded() {
+                what_constitutes_software_query::print_warning_summary();
+                eprintln!("ERROR: too many projects skipped (see --max-warnings), the last sample is incomplete.");
+                std::process::exit(1);
+            }
+        }
+    }
+
+    macro_rules! prepare_database {
+        ($savepoint:expr, $stores:expr) => {
+            Djanco::from_spec(dataset, cache, $savepoint, $stores, log.clone())
+                .expect("Error initializing Djanco!")
+        }
+    }
+
+    let database = prepare_database!(options.savepoint, stores!(Generic));
+    let directories = match options.config.as_ref() {
+        Some(config) => what_constitutes_software_query::config::run_config(&database, &log, &options.djanco.output_path, config)
+            .expect("Error running the configured queries"),
+        None => {
+            execute_query!(database, what_constitutes_software_query::sample_stars_scala);
+            execute_query!(database, what_constitutes_software_query::sample_all_scala);
+            execute_query!(database, what_constitutes_software_query::sample_developed_scala);
+            execute_query!(database, what_constitutes_software_query::sample_stateful_scala);
+            execute_query!(database, what_constitutes_software_query::sample_internationalized_scala);
+            execute_query!(database, what_constitutes_software_query::sample_web_aware_scala);
+            execute_query!(database, what_constitutes_software_query::sample_stars_scala_licensed);
+            execute_query!(database, what_constitutes_software_query::sample_with_tests_scala);
+            execute_query!(database, what_constitutes_software_query::sample_governed_community_scala);
+            execute_query!(database, what_constitutes_software_query::sample_community_standards_scala);
+            execute_query!(database, what_constitutes_software_query::sample_evolved_scala);
+            execute_query!(database, what_constitutes_software_query::sample_microservices_scala);
+            execute_query!(database, what_constitutes_software_query::sample_low_duplication_scala);
+            execute_query!(database, what_constitutes_software_query::sample_stratified_by_locs_scala);
+            execute_query!(database, what_constitutes_software_query::sample_bottom_stars_scala);
+            execute_query!(database, what_constitutes_software_query::sample_community_healthy_scala);
+            execute_query!(database, what_constitutes_software_query::sample_with_legacy_code_scala);
+            execute_query!(database, what_constitutes_software_query::sample_platform_agnostic_scala);
+            execute_query!(database, what_constitutes_software_query::sample_platform_specific_scala);
+            execute_query!(database, what_constitutes_software_query::sample_early_adopter_led_scala);
+            execute_query!(database, what_constitutes_software_query::sample_with_hotspot_scala);
+            execute_query!(database, what_constitutes_software_query::sample_ci_scala);
+            execute_query!(database, what_constitutes_software_query::sample_semantic_release_scala);
+            execute_query!(database, what_constitutes_software_query::sample_docker_compose_scala);
+            execute_query!(database, what_constitutes_software_query::sample_forks_of_mature_scala);
+            execute_query!(database, what_constitutes_software_query::sample_forks_of_young_scala);
+            execute_query!(database, what_constitutes_software_query::sample_automated_scala);
+            execute_query!(database, what_constitutes_software_query::sample_sustained_team_scala);
+            execute_query!(database, what_constitutes_software_query::sample_high_engagement_scala);
+            execute_query!(database, what_constitutes_software_query::sample_clean_history_scala);
+            execute_query!(database, what_constitutes_software_query::sample_container_tests_scala);
+            execute_query!(database, what_constitutes_software_query::sample_fully_automated_scala);
+            execute_query!(database, what_constitutes_software_query::sample_financially_sustainable_scala);
+            execute_query!(database, what_constitutes_software_query::sample_peaked_scala);
+            execute_query!(database, what_constitutes_software_query::sample_medium_size_scala);
+            execute_query!(database, what_constitutes_software_query::sample_large_files_scala);
+            execute_query!(database, what_constitutes_software_query::sample_small_files_scala);
+            execute_query!(database, what_constitutes_software_query::sample_with_multiple_branches_scala);
+            execute_query!(database, what_constitutes_software_query::sample_hand_written_scala);
+            execute_query!(database, what_constitutes_software_query::sample_stable_scala);
+            execute_query!(database, what_constitutes_software_query::sample_container_aware_scala);
+            execute_query!(database, what_constitutes_software_query::sample_monitored_scala);
+            execute_query!(database, what_constitutes_software_query::sample_issue_tracked_commits_scala);
+            execute_query!(database, what_constitutes_software_query::sample_low_test_debt_scala);
+            execute_query!(database, what_constitutes_software_query::sample_matrix_ci_scala);
+            execute_query!(database, what_constitutes_software_query::sample_code_repositories_scala);
+            execute_query!(database, what_constitutes_software_query::sample_copyrighted_scala);
+            execute_query!(database, what_constitutes_software_query::sample_linted_scala);
+            execute_query!(database, what_constitutes_software_query::sample_scoped_scala);
+            vec![options.djanco.output_path.clone()]
+        }
+    };
+
+
+    for directory in directories.iter() {
+        what_constitutes_software_query::output::export_samples(directory, options.output_format)
+            .expect("Error exporting samples");
+        if options.include_url {
+            what_constitutes_software_query::output::write_url_files(&database, directory)
+                .expect("Error writing project URLs");
+        }
+        if options.compress {
+            what_constitutes_software_query::output::compress_samples(directory)
+                .expect("Error compressing samples");
+        }
+    }
+    what_constitutes_software_query::print_warning_summary();
+
+    if options.djanco.repository.is_some() && !options.djanco.do_not_archive_results {
+        add_results(PROJECT_NAME, &repository.unwrap(), &options.djanco.output_path, options.djanco.size_limit);
+    }
+}
@@ -0,0 +1,245 @@
+// Sampling configurations read from a TOML file given with --config, to draw several custom samples
+// in one run instead of the built-in ones:
+//
+//   [[query]]
+//   name = "java_popular"     # written to java_popular.csv (and java_popular.json)
+//   language = "java"         # java, python, javascript, scala, cpp, typescript, go, or csharp
+//   selection_size = 500      # default: SELECTION_SIZE
+//   seed = 7                  # default: 1
+//   output = "popular"        # subdirectory of --output-path (relative, without ..), default: --output-path itself
+//
+//   [query.filters]
+//   min_stars = 100
+//   max_users = 5
+//
+// Filters are bounds on project attributes, min_ATTRIBUTE or max_ATTRIBUTE (both inclusive), see
+// Attribute for the attributes.
+
+use std::path::{Component, Path, PathBuf};
+
+use djanco::database::*;
+use djanco::log::*;
+use djanco::objects::*;
+use djanco::time::Duration;
+
+use crate::output::manifest::QueryParams;
+use crate::{_sample_random_mapped, language_from_name, map_to_output_format, print_warning_summary, reset_progress,
+            warning_limit_exceeded, SELECTION_SIZE};
+
+const DEFAULT_SEED: u128 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Attribute {
+    Stars,
+    Commits,
+    Users,
+    Locs,
+    Snapshots,
+    AgeDays,
+    MaxHIndex,
+    Issues,
+}
+
+impl Attribute {
+    pub fn from_name(name: &str) -> Option<Attribute> {
+        match name {
+            "stars" => Some(Attribute::Stars),
+            "commits" => Some(Attribute::Commits),
+            "users" => Some(Attribute::Users),
+            "locs" => Some(Attribute::Locs),
+            "snapshots" => Some(Attribute::Snapshots),
+            "age_days" => Some(Attribute::AgeDays),
+            "max_h_index" => Some(Attribute::MaxHIndex),
+            "issues" => Some(Attribute::Issues),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Attribute::Stars => "stars",
+            Attribute::Commits => "commits",
+            Attribute::Users => "users",
+            Attribute::Locs => "locs",
+            Attribute::Snapshots => "snapshots",
+            Attribute::AgeDays => "age_days",
+            Attribute::MaxHIndex => "max_h_index",
+            Attribute::Issues => "issues",
+        }
+    }
+
+    // None if the project does not have the attribute in the dataset.
+    pub fn value(&self, project: &ItemWithData<Project>) -> Option<u64> {
+        match self {
+            Attribute::Stars => project.star_count().map(|count| count as u64),
+            Attribute::Commits => project.commit_count().map(|count| count as u64),
+            Attribute::Users => project.user_count().map(|count| count as u64),
+            Attribute::Locs => project.locs().map(|count| count as u64),
+            Attribute::Snapshots => project.snapshot_count().map(|count| count as u64),
+            Attribute::AgeDays => project.age().map(|age| age.as_secs() / Duration::from_days(1).as_secs()),
+            Attribute::MaxHIndex => project.max_h_index1(),
+            Attribute::Issues => project.issue_count().map(|count| count as u64),
+        }
+    }
+}
+
+// One bound from [query.filters], e.g. min_stars = 100.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Filter {
+    AtLeast(Attribute, u64),
+    AtMost(Attribute, u64),
+}
+
+impl Filter {
+    pub fn accepts(&self, project: &ItemWithData<Project>) -> bool {
+        match self {
+            Filter::AtLeast(attribute, min) => attribute.value(project).is_some_and(|value| value >= *min),
+            Filter::AtMost(attribute, max) => attribute.value(project).is_some_and(|value| value <= *max),
+        }
+    }
+}
+
+impl std::fmt::Display for Filter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Filter::AtLeast(attribute, min) => write!(f, "min_{} = {}", attribute.name(), min),
+            Filter::AtMost(attribute, max) => write!(f, "max_{} = {}", attribute.name(), max),
+        }
+    }
+}
+
+// One [[query]] of the configuration file.
+#[derive(Clone, Debug)]
+pub struct QueryConfig {
+    pub name: String,
+    pub language: Language,
+    pub selection_size: usize,
+    pub seed: u128,
+    pub output: Option<PathBuf>,
+    pub filters: Vec<Filter>,
+}
+
+impl QueryConfig {
+    pub fn accepts(&self, project: &ItemWithData<Project>) -> bool {
+        self.filters.iter().all(|filter| filter.accepts(project))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SamplingConfig {
+    pub queries: Vec<QueryConfig>,
+}
+
+fn _invalid(path: &Path, message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), message))
+}
+
+// Whether a path stays inside the directory it is joined to: relative, and without `..`.
+fn _stays_inside(path: &Path) -> bool {
+    path.components().all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+// Whether a query name can be used as a file name in the output directory: not empty, `.`, or `..`,
+// and without path separators.
+fn _is_file_name(name: &str) -> bool {
+    !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\'])
+}
+
+fn _query(path: &Path, index: usize, query: &toml::Value) -> Result<QueryConfig, std::io::Error> {
+    let invalid = |message: String| _invalid(path, format!("query {}: {}", index + 1, message));
+    let name = query.get("name").and_then(|name| name.as_str())
+        .ok_or_else(|| invalid("missing name".to_owned()))?;
+    if !_is_file_name(name) {
+        return Err(invalid(format!("name {:?} must be a file name (without / or \\, and not . or ..)", name)))
+    }
+    let language = query.get("language").and_then(|language| language.as_str())
+        .ok_or_else(|| invalid("missing language".to_owned()))?;
+    let language = language_from_name(language)
+        .ok_or_else(|| invalid(format!("unknown language {:?}", language)))?;
+    let number = |key: &str| -> Result<Option<u64>, std::io::Error> {
+        match query.get(key) {
+            Some(value) => value.as_integer().filter(|value| *value >= 0)
+                .map(|value| Some(value as u64))
+                .ok_or_else(|| invalid(format!("{} must be a non-negative integer", key))),
+            None => Ok(None),
+        }
+    };
+    let selection_size = number("selection_size")?.map(|size| size as usize).unwrap_or(SELECTION_SIZE);
+    let seed = number("seed")?.map(|seed| seed as u128).unwrap_or(DEFAULT_SEED);
+    let output = match query.get("output") {
+        Some(output) => Some(PathBuf::from(output.as_str().ok_or_else(|| invalid("output must be a string".to_owned()))?)),
+        None => None,
+    };
+    if let Some(output) = output.as_ref().filter(|output| !_stays_inside(output)) {
+        return Err(invalid(format!("output {} must be a subdirectory of the output path (relative, without ..)", output.display())))
+    }
+    let mut filters = Vec::new();
+    if let Some(table) = query.get("filters") {
+        let table = table.as_table().ok_or_else(|| invalid("filters must be a table".to_owned()))?;
+        for (key, value) in table.iter() {
+            let value = value.as_integer().filter(|value| *value >= 0)
+                .ok_or_else(|| invalid(format!("filter {} must be a non-negative integer", key)))? as u64;
+            let filter = match (key.strip_prefix("min_"), key.strip_prefix("max_")) {
+                (Some(attribute), _) => Attribute::from_name(attribute).map(|attribute| Filter::AtLeast(attribute, value)),
+                (_, Some(attribute)) => Attribute::from_name(attribute).map(|attribute| Filter::AtMost(attribute, value)),
+                _ => None,
+            };
+            filters.push(filter.ok_or_else(|| invalid(format!("unknown filter {}", key)))?);
+        }
+    }
+    Ok(QueryConfig { name: name.to_owned(), language, selection_size, seed, output, filters })
+}
+
+impl SamplingConfig {
+    pub fn from_file(path: &Path) -> Result<SamplingConfig, std::io::Error> {
+        let contents = std::fs::read_to_string(path)?;
+        let document = contents.parse::<toml::Value>()
+            .map_err(|error| _invalid(path, error.to_string()))?;
+        let queries = match document.get("query") {
+            Some(queries) => queries.as_array().ok_or_else(|| _invalid(path, "query must be an array of tables ([[query]])".to_owned()))?,
+            None => return Err(_invalid(path, "no queries defined".to_owned())),
+        };
+        let queries = queries.iter().enumerate()
+            .map(|(index, query)| _query(path, index, query))
+            .collect::<Result<Vec<QueryConfig>, std::io::Error>>()?;
+        Ok(SamplingConfig { queries })
+    }
+}
+
+// Random sample of projects in the query's language that pass its filters, written to NAME.csv.
+pub fn sample_configured(database: &Database, _log: &Log, output: &Path, query: &QueryConfig) -> Result<(), std::io::Error> {
+    let output = match query.output.as_ref() {
+        Some(directory) => output.join(directory),
+        None => output.to_path_buf(),
+    };
+    let filters: Vec<String> = query.filters.iter().map(|filter| filter.to_string()).collect();
+    let filters: Vec<&str> = filters.iter().map(|filter| filter.as_str()).collect();
+    _sample_random_mapped(database, &output, &QueryParams::new(query.language.clone(), Some(query.seed), query.selection_size, &filters),
+                          |project| query.accepts(project),
+                          map_to_output_format,
+                          &format!("{}.csv", query.name))
+}
+
+// Run all the queries of a configuration file one after another. Returns the directories written
+// to, for exporting and compressing the samples afterwards.
+pub fn run_config(database: &Database, log: &Log, output: &Path, path: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
+    let config = SamplingConfig::from_file(path)?;
+    let mut directories: Vec<PathBuf> = Vec::new();
+    for query in config.queries.iter() {
+        reset_progress();
+        eprintln!("Sampling {}", query.name);
+        sample_configured(database, log, output, query)?;
+        if warning_limit_exceeded() {
+            print_warning_summary();
+            return Err(std::io::Error::other(format!("too many projects skipped (see --max-warnings), sample {} is incomplete", query.name)))
+        }
+        let directory = match query.output.as_ref() {
+            Some(directory) => output.join(directory),
+            None => output.to_path_buf(),
+        };
+        if !directories.contains(&directory) {
+            directories.push(directory);
+        }
+    }
+    Ok(directories)
+}
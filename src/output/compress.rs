@@ -0,0 +1,23 @@
+// Gzip-compressed CSV output, for samples that include many snapshots per project.
+
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use flate2::Compression;
+use flate2::write::GzEncoder;
+
+use crate::write_output_file;
+
+// Compress a file into FILE.gz and remove the original. The original is only removed once FILE.gz
+// is complete.
+pub fn gzip_file(path: &Path) -> Result<PathBuf, std::io::Error> {
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let compressed = format!("{}.gz", path.file_name().unwrap().to_string_lossy());
+    write_output_file(dir, &compressed, |file| {
+        let mut encoder = GzEncoder::new(File::create(dir.join(file))?, Compression::default());
+        std::io::copy(&mut File::open(path)?, &mut encoder)?;
+        encoder.finish().map(|_| ())
+    })?;
+    std::fs::remove_file(path)?;
+    Ok(dir.join(compressed))
+}
@@ -0,0 +1,74 @@
+// Manifests: a JSON file next to each sample recording how it was drawn, so that it can be
+// reproduced later.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use djanco::objects::*;
+
+use crate::{min_commit_ratio, output_file_name, savepoint, write_output_file};
+
+// Commit of this crate the queries were built from, set by build.rs.
+const GIT_COMMIT: &str = env!("GIT_HASH");
+
+// The parameters a sample was drawn with. Filters are descriptions of the filters applied on top
+// of the language, in the order they are applied.
+#[derive(Clone, Debug)]
+pub struct QueryParams {
+    pub savepoint: i64,
+    pub seed: Option<u128>,
+    pub selection_size: usize,
+    pub min_commit_ratio: f64,
+    pub language: Language,
+    pub filters: Vec<String>,
+}
+
+impl QueryParams {
+    // Parameters of a sample drawn at the configured savepoint and minimum commit ratio. Samples of
+    // the top starred projects have no seed.
+    pub fn new(language: Language, seed: Option<u128>, selection_size: usize, filters: &[&str]) -> QueryParams {
+        QueryParams {
+            savepoint: savepoint(),
+            seed,
+            selection_size,
+            min_commit_ratio: min_commit_ratio(),
+            language,
+            filters: filters.iter().map(|filter| filter.to_string()).collect(),
+        }
+    }
+}
+
+fn _json_string(string: &str) -> String {
+    let mut json = String::from("\"");
+    for c in string.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+// Write SAMPLE_NAME.json (with the output prefix, if any) into the output directory.
+pub fn write_manifest(output: &Path, sample_name: &str, params: &QueryParams) -> Result<(), std::io::Error> {
+    let seed = params.seed.map(|seed| seed.to_string()).unwrap_or_else(|| "null".to_owned());
+    let filters: Vec<String> = params.filters.iter().map(|filter| _json_string(filter)).collect();
+    write_output_file(output, &output_file_name(&format!("{}.json", sample_name)), |file| {
+        let mut manifest = File::create(output.join(file))?;
+        writeln!(manifest, "{{")?;
+        writeln!(manifest, "  \"sample\": {},", _json_string(sample_name))?;
+        writeln!(manifest, "  \"savepoint\": {},", params.savepoint)?;
+        writeln!(manifest, "  \"seed\": {},", seed)?;
+        writeln!(manifest, "  \"selection_size\": {},", params.selection_size)?;
+        writeln!(manifest, "  \"min_commit_ratio\": {},", params.min_commit_ratio)?;
+        writeln!(manifest, "  \"language\": {},", _json_string(&params.language.to_string()))?;
+        writeln!(manifest, "  \"filters\": [{}],", filters.join(", "))?;
+        writeln!(manifest, "  \"git_commit\": {}", _json_string(GIT_COMMIT))?;
+        writeln!(manifest, "}}")
+    })
+}
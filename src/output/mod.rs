@@ -0,0 +1,230 @@
+// Exporting samples in formats other than CSV. Djanco queries always write CSV files; these are
+// converted afterwards, so every sample_* function supports every format.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use clap::ArgEnum;
+use djanco::database::*;
+use djanco::objects::*;
+
+use crate::{write_output_file, EXTENDED_URL_HEADERS, HEADERS};
+
+pub mod manifest;
+pub mod stats;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "compress")]
+pub mod compress;
+
+// One row of a sample: the columns in HEADERS.
+pub type SampleRow = (ProjectId, String, SnapshotId);
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Csv,
+    Parquet,
+    Sqlite,
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Csv => "csv",
+            OutputFormat::Parquet => "parquet",
+            OutputFormat::Sqlite => "sqlite",
+        }
+    }
+
+    // Cargo feature that enables the format, None for CSV which is always built in.
+    pub fn feature(&self) -> Option<&'static str> {
+        match self {
+            OutputFormat::Csv => None,
+            OutputFormat::Parquet => Some("parquet"),
+            OutputFormat::Sqlite => Some("sqlite"),
+        }
+    }
+
+    // Whether this build can write the format; the non-CSV formats are behind feature flags.
+    pub fn is_supported(&self) -> bool {
+        match self {
+            OutputFormat::Csv => true,
+            OutputFormat::Parquet => cfg!(feature = "parquet"),
+            OutputFormat::Sqlite => cfg!(feature = "sqlite"),
+        }
+    }
+}
+
+// Split a CSV line into fields, handling double-quoted fields with escaped ("") quotes.
+fn _split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => { field.push('"'); chars.next(); }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+// Read the rows of a sample written with HEADERS. None if the file has other columns (e.g. the
+// stratified samples), which cannot be exported in the fixed sample schema.
+pub fn read_sample_csv(path: &Path) -> Result<Option<Vec<SampleRow>>, std::io::Error> {
+    let invalid = |number: usize, what: &str| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}:{}: {}", path.display(), number + 1, what))
+    };
+    let mut lines = BufReader::new(File::open(path)?).lines();
+    let header = match lines.next() {
+        Some(header) => _split_csv_line(&header?),
+        None => return Ok(Some(Vec::new())),
+    };
+    if header != HEADERS {
+        return Ok(None)
+    }
+    let mut rows = Vec::new();
+    for (number, line) in lines.enumerate().map(|(number, line)| (number + 1, line)) {
+        let fields = _split_csv_line(&line?);
+        if fields.len() != HEADERS.len() {
+            return Err(invalid(number, "wrong number of columns"))
+        }
+        let pid = fields[0].parse::<u64>().map_err(|_| invalid(number, "invalid pid"))?;
+        let hash_id = fields[2].parse::<u64>().map_err(|_| invalid(number, "invalid hash_id"))?;
+        rows.push((ProjectId::from(pid), fields[1].clone(), SnapshotId::from(hash_id)));
+    }
+    Ok(Some(rows))
+}
+
+// Write every CSV sample in the output directory in the given format too, next to the CSV file
+// (e.g. sample_stars.csv -> sample_stars.parquet). The CSV files are kept, so the summaries and
+// archived results stay the same regardless of format.
+pub fn export_samples(output: &Path, format: OutputFormat) -> Result<(), std::io::Error> {
+    if format == OutputFormat::Csv {
+        return Ok(())
+    }
+    for entry in std::fs::read_dir(output)? {
+        let csv = entry?.path();
+        if csv.extension() != Some("csv".as_ref()) || stats::is_extension_stats(&csv) {
+            continue
+        }
+        let rows = match read_sample_csv(&csv)? {
+            Some(rows) => rows,
+            None => {
+                eprintln!("WARNING: {} does not have the sample columns, not exporting it as {}.", csv.display(), format.extension());
+                continue
+            }
+        };
+        let exported = csv.with_extension(format.extension());
+        let exported = exported.file_name().unwrap().to_string_lossy();
+        write_output_file(output, &exported, |file| _write(&rows, &output.join(file), format))?;
+    }
+    Ok(())
+}
+
+fn _csv_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+// Write a companion SAMPLE_url.csv with EXTENDED_URL_HEADERS next to every CSV sample in the output
+// directory, adding the URL of each project, e.g. to clone it. Run this after export_samples, which
+// would warn about the companion files.
+pub fn write_url_files(database: &Database, output: &Path) -> Result<(), std::io::Error> {
+    let mut samples: Vec<(String, Vec<SampleRow>)> = Vec::new();
+    for entry in std::fs::read_dir(output)? {
+        let csv = entry?.path();
+        if csv.extension() != Some("csv".as_ref()) {
+            continue
+        }
+        if let Some(rows) = read_sample_csv(&csv)? {
+            samples.push((csv.file_stem().unwrap().to_string_lossy().to_string(), rows));
+        }
+    }
+    let project_ids: HashSet<ProjectId> = samples.iter()
+        .flat_map(|(_, rows)| rows.iter().map(|(project_id, _, _)| *project_id))
+        .collect();
+    let urls: HashMap<ProjectId, String> = database.projects()
+        .filter(|project| project_ids.contains(&project.id()))
+        .map(|project| (project.id(), project.url()))
+        .collect();
+    for (sample, rows) in samples.iter() {
+        write_output_file(output, &format!("{}_url.csv", sample), |file| {
+            let mut url_file = File::create(output.join(file))?;
+            writeln!(url_file, "{}", EXTENDED_URL_HEADERS.join(","))?;
+            for (project_id, path, snapshot_id) in rows.iter() {
+                let url = urls.get(project_id).map(String::as_str).unwrap_or("");
+                writeln!(url_file, "{},{},{},{}", project_id, _csv_field(url), _csv_field(path), snapshot_id)?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(())
+}
+
+// Gzip every CSV sample in the output directory (sample_stars.csv -> sample_stars.csv.gz). Run this
+// after export_samples, which reads the CSV files.
+pub fn compress_samples(output: &Path) -> Result<(), std::io::Error> {
+    for entry in std::fs::read_dir(output)? {
+        let csv = entry?.path();
+        if csv.extension() == Some("csv".as_ref()) {
+            _gzip(&csv)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "compress")]
+fn _gzip(path: &Path) -> Result<(), std::io::Error> {
+    compress::gzip_file(path).map(|_| ())
+}
+
+#[cfg(not(feature = "compress"))]
+fn _gzip(path: &Path) -> Result<(), std::io::Error> {
+    Err(_unsupported(path, "compressed output"))
+}
+
+fn _write(rows: &[SampleRow], path: &Path, format: OutputFormat) -> Result<(), std::io::Error> {
+    match format {
+        OutputFormat::Csv => Ok(()),
+        OutputFormat::Parquet => _write_parquet(rows, path),
+        OutputFormat::Sqlite => _write_sqlite(rows, path),
+    }
+}
+
+fn _unsupported(path: &Path, what: &str) -> std::io::Error {
+    std::io::Error::other(format!("{}: this build does not support {}", path.display(), what))
+}
+
+#[cfg(feature = "parquet")]
+fn _write_parquet(rows: &[SampleRow], path: &Path) -> Result<(), std::io::Error> {
+    parquet::write_parquet(rows, path)
+        .map_err(|error| std::io::Error::other(format!("{}: {}", path.display(), error)))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn _write_parquet(_rows: &[SampleRow], path: &Path) -> Result<(), std::io::Error> {
+    Err(_unsupported(path, "parquet output"))
+}
+
+#[cfg(feature = "sqlite")]
+fn _write_sqlite(rows: &[SampleRow], path: &Path) -> Result<(), std::io::Error> {
+    sqlite::write_sqlite(rows, path)
+        .map_err(|error| std::io::Error::other(format!("{}: {}", path.display(), error)))
+}
+
+#[cfg(not(feature = "sqlite"))]
+fn _write_sqlite(_rows: &[SampleRow], path: &Path) -> Result<(), std::io::Error> {
+    Err(_unsupported(path, "sqlite output"))
+}
@@ -0,0 +1,53 @@
+// Parquet output, for loading samples into Pandas and other data pipelines.
+
+use std::fs::File;
+use std::path::Path;
+
+use arrow2::array::{UInt64Array, Utf8Array};
+use arrow2::chunk::Chunk;
+use arrow2::datatypes::{DataType, Field, Schema};
+use arrow2::io::parquet::write::{transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions};
+use djanco::objects::*;
+
+pub use arrow2::error::Error as ParquetError;
+
+use crate::HEADERS;
+
+// Same columns as the CSV samples (HEADERS): pid and hash_id are numeric ids, path is a string.
+fn _schema() -> Schema {
+    Schema::from(vec![
+        Field::new(HEADERS[0], DataType::UInt64, false),
+        Field::new(HEADERS[1], DataType::Utf8, false),
+        Field::new(HEADERS[2], DataType::UInt64, false),
+    ])
+}
+
+pub fn write_parquet(rows: &[(ProjectId, String, SnapshotId)], path: &Path) -> Result<(), ParquetError> {
+    let pids: Vec<u64> = rows.iter().map(|(pid, _, _)| (*pid).into()).collect();
+    let paths: Vec<&str> = rows.iter().map(|(_, path, _)| path.as_str()).collect();
+    let hash_ids: Vec<u64> = rows.iter().map(|(_, _, hash_id)| (*hash_id).into()).collect();
+    let chunk = Chunk::new(vec![
+        UInt64Array::from_vec(pids).boxed(),
+        Utf8Array::<i32>::from_slice(paths).boxed(),
+        UInt64Array::from_vec(hash_ids).boxed(),
+    ]);
+
+    let schema = _schema();
+    let options = WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Snappy,
+        version: Version::V2,
+        data_pagesize_limit: None,
+    };
+    let encodings = schema.fields.iter()
+        .map(|field| transverse(&field.data_type, |_| Encoding::Plain))
+        .collect();
+    let row_groups = RowGroupIterator::try_new(vec![Ok(chunk)].into_iter(), &schema, options, encodings)?;
+
+    let mut writer = FileWriter::try_new(File::create(path)?, schema, options)?;
+    for group in row_groups {
+        writer.write(group?)?;
+    }
+    writer.end(None)?;
+    Ok(())
+}
@@ -0,0 +1,28 @@
+// SQLite output, for joining samples against other datasets with SQL.
+
+use std::convert::TryFrom;
+use std::path::Path;
+
+use djanco::objects::*;
+use rusqlite::{params, Connection};
+
+// Write the rows into a `samples` table, replacing the table if the database already has one.
+pub fn write_sqlite(rows: &[(ProjectId, String, SnapshotId)], db_path: &Path) -> Result<(), rusqlite::Error> {
+    let mut connection = Connection::open(db_path)?;
+    connection.execute_batch("
+        DROP TABLE IF EXISTS samples;
+        CREATE TABLE samples (pid INTEGER, path TEXT, hash_id TEXT);
+    ")?;
+    // One transaction for all rows, otherwise SQLite syncs to disk after every insert.
+    let transaction = connection.transaction()?;
+    {
+        let mut insert = transaction.prepare("INSERT INTO samples (pid, path, hash_id) VALUES (?1, ?2, ?3)")?;
+        for (pid, path, hash_id) in rows {
+            // SQLite integers are signed, so ids past i64::MAX are an error rather than wrapped.
+            let pid = i64::try_from(u64::from(*pid))
+                .map_err(|error| rusqlite::Error::ToSqlConversionFailure(Box::new(error)))?;
+            insert.execute(params![pid, path, hash_id.to_string()])?;
+        }
+    }
+    transaction.commit()
+}
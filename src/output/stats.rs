@@ -0,0 +1,50 @@
+// Statistics of the rows of a sample, written next to it, e.g. to check that a Java sample really
+// contains mostly .java files.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+
+use crate::output::{SampleRow, _csv_field};
+use crate::{output_file_name, write_output_file};
+
+const EXTENSION_STATS_HEADERS: [&str; 3] = ["extension", "count", "fraction"];
+
+// The extension of a path with its dot (".java"), or "" for files without one.
+fn _extension(path: &str) -> String {
+    Path::new(path).extension()
+        .map(|extension| format!(".{}", extension.to_string_lossy()))
+        .unwrap_or_default()
+}
+
+// How many rows have each extension, the most common first (ties by extension).
+pub fn extension_counts(rows: &[SampleRow]) -> Vec<(String, usize)> {
+    let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+    for (_, path, _) in rows.iter() {
+        *counts.entry(_extension(path)).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|(a_extension, a_count), (b_extension, b_count)| b_count.cmp(a_count).then_with(|| a_extension.cmp(b_extension)));
+    counts
+}
+
+// Whether a CSV file in the output directory holds the extension statistics of a sample rather than
+// a sample.
+pub fn is_extension_stats(csv: &Path) -> bool {
+    csv.file_name().is_some_and(|name| name.to_string_lossy().ends_with("_extension_stats.csv"))
+}
+
+// Write SAMPLE_extension_stats.csv (with the output prefix, if any) into the output directory, with
+// the count and fraction of the rows of the sample of each extension.
+pub fn write_extension_stats(rows: &[SampleRow], output: &Path, sample_name: &str) -> Result<(), std::io::Error> {
+    let counts = extension_counts(rows);
+    write_output_file(output, &output_file_name(&format!("{}_extension_stats.csv", sample_name)), |file| {
+        let mut stats = File::create(output.join(file))?;
+        writeln!(stats, "{}", EXTENSION_STATS_HEADERS.join(","))?;
+        for (extension, count) in counts.iter() {
+            writeln!(stats, "{},{},{:.6}", _csv_field(extension), count, *count as f64 / rows.len() as f64)?;
+        }
+        Ok(())
+    })
+}
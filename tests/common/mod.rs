@@ -0,0 +1,14 @@
+// Tests that need projects load them from a real dataset, since djanco cannot build projects in
+// memory. They are ignored unless asked for, with a dataset:
+//
+//   DJANCO_DATASET=/path/to/dataset DJANCO_CACHE=/path/to/cache cargo test -- --ignored
+use djanco::*;
+use djanco::database::*;
+use djanco::log::*;
+
+pub fn database() -> Database {
+    let dataset = std::env::var("DJANCO_DATASET").expect("DJANCO_DATASET is not set, but this test needs a dataset");
+    let cache = std::env::var("DJANCO_CACHE").unwrap_or_else(|_| format!("{}/cache", dataset));
+    Djanco::from_spec(dataset, cache, 1606780800 /* = December 2020*/, stores!(Generic), Log::new(Verbosity::Warning))
+        .expect("Error initializing Djanco!")
+}
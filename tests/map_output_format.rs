@@ -0,0 +1,111 @@
+// _map_to_output_format on every project in the dataset: projects that cannot be converted give
+// None with the warning for the first missing piece, the others give one row per file at HEAD.
+mod common;
+
+use djanco::objects::*;
+
+use what_constitutes_software_query::{_map_to_output_format, can_map_to_output_format, map_to_output_format, normalize_path, reset_progress, SamplingContext, SamplingWarning};
+
+// The warning _map_to_output_format should skip the project with, worked out step by step.
+fn expected_skip(project: &ItemWithData<Project>) -> Option<SamplingWarning> {
+    let project_id = project.id();
+    let default_branch = match project.default_branch() {
+        Some(default_branch) => default_branch,
+        None => return Some(SamplingWarning::NoDefaultBranch(project_id)),
+    };
+    let heads = match project.heads_with_data() {
+        Some(heads) => heads,
+        None => return Some(SamplingWarning::NoHeads(project_id)),
+    };
+    let default_head = heads.into_iter().find(|head| head.name() == format!("refs/heads/{}", default_branch));
+    let default_head = match default_head {
+        Some(default_head) => default_head,
+        None => return Some(SamplingWarning::NoDefaultHead(project_id)),
+    };
+    match default_head.commit_with_data() {
+        Some(_) => None,
+        None => Some(SamplingWarning::NoHeadCommit(project_id, default_head.commit_id())),
+    }
+}
+
+fn default_head_count(project: &ItemWithData<Project>) -> usize {
+    let default_branch = format!("refs/heads/{}", project.default_branch().unwrap());
+    project.heads_with_data().unwrap().iter().filter(|head| head.name() == default_branch).count()
+}
+
+#[test]
+#[ignore = "needs DJANCO_DATASET"]
+fn warnings_match_failures() {
+    let database = common::database();
+    let mut seen = std::collections::HashSet::new();
+    for project in database.projects() {
+        let mut context = SamplingContext::new();
+        let result = _map_to_output_format(&project, &mut context);
+        let skipped: Vec<&SamplingWarning> = context.warnings.iter().filter(|warning| warning.skips_project()).collect();
+        match expected_skip(&project) {
+            Some(expected) => {
+                assert!(result.is_none(), "project {} should be skipped with {}", project.id(), expected);
+                assert_eq!(skipped, vec![&expected]);
+                seen.insert(expected.label());
+            }
+            None => {
+                assert!(result.is_some(), "project {} should convert", project.id());
+                assert!(skipped.is_empty(), "project {} converted, but warned {:?}", project.id(), skipped);
+                let multiple = context.warnings.contains(&SamplingWarning::MultipleDefaultHeads(project.id(), default_head_count(&project)));
+                assert_eq!(multiple, default_head_count(&project) > 1);
+                if multiple {
+                    seen.insert("multiple-default-heads");
+                }
+            }
+        }
+    }
+    eprintln!("Warning paths covered by the dataset: {:?}", seen);
+}
+
+#[test]
+#[ignore = "needs DJANCO_DATASET"]
+fn rows_match_head_tree() {
+    let database = common::database();
+    for project in database.projects().filter(|project| expected_skip(project).is_none()) {
+        let rows = _map_to_output_format(&project, &mut SamplingContext::new()).unwrap();
+        let default_branch = format!("refs/heads/{}", project.default_branch().unwrap());
+        let head = project.heads_with_data().unwrap().into_iter().find(|head| head.name() == default_branch).unwrap();
+        let expected: Vec<(ProjectId, String, SnapshotId)> = head.commit_with_data().unwrap().tree_with_data()
+            .changes_with_data().into_iter()
+            .flat_map(|change| Some((project.id(), normalize_path(&change.path()?.location()), change.snapshot_id()?)))
+            .collect();
+        assert_eq!(rows, expected, "rows for project {}", project.id());
+    }
+}
+
+// map_to_output_format reuses the conversion done by can_map_to_output_format.
+#[test]
+#[ignore = "needs DJANCO_DATASET"]
+fn cached_rows_match_fresh_rows() {
+    let database = common::database();
+    reset_progress();
+    for project in database.projects() {
+        let fresh = _map_to_output_format(&project, &mut SamplingContext::new());
+        assert_eq!(can_map_to_output_format(&project), fresh.is_some());
+        assert_eq!(map_to_output_format(project.clone()), fresh, "rows for project {}", project.id());
+    }
+}
+
+#[test]
+fn skipped_projects_counted_once() {
+    let mut context = SamplingContext::new();
+    let project_id = ProjectId::from(1u64);
+    context.warn(SamplingWarning::NoDefaultBranch(project_id));
+    context.warn(SamplingWarning::NoDefaultBranch(project_id));
+    context.warn(SamplingWarning::MultipleDefaultHeads(ProjectId::from(2u64), 2));
+    assert_eq!(context.skipped_project_count(), 1);
+    assert_eq!(context.summary(), vec!["1 projects skipped: 1 no-default-branch".to_owned(), "1 other warnings: 1 multiple-default-heads".to_owned()]);
+}
+
+#[test]
+fn paths_normalized() {
+    assert_eq!(normalize_path("src/main.rs"), "src/main.rs");
+    assert_eq!(normalize_path("./src//main.rs"), "src/main.rs");
+    assert_eq!(normalize_path("/src/main.rs"), "src/main.rs");
+    assert_eq!(normalize_path("src\\main.rs"), "src/main.rs");
+}
@@ -0,0 +1,93 @@
+// Properties of the helpers that post-process output rows, checked on random rows.
+// map_to_output_format_filtered needs a project from a dataset, so its filtering step,
+// filter_by_extensions, is checked directly.
+use djanco::objects::*;
+use djanco::time::Duration;
+use proptest::prelude::*;
+
+use what_constitutes_software_query::{deduplicate_snapshots, filter_by_extensions, limit_snapshots, percentile_value, AgeBracket};
+use what_constitutes_software_query::output::stats::extension_counts;
+
+const EXTENSIONS: [&str; 4] = [".java", ".py", ".js", ".scala"];
+
+// Rows from a handful of projects and snapshots, so that duplicates are common.
+fn rows() -> impl Strategy<Value = Vec<(ProjectId, String, SnapshotId)>> {
+    let path = (
+        prop::sample::select(vec!["Main", "src/util", "lib/index", "test/test_main", "README"]),
+        prop::sample::select(vec![".java", ".py", ".js", ".scala", ".md", ".java.orig", ""]),
+    ).prop_map(|(name, extension)| format!("{}{}", name, extension));
+    let row = (0u64..5, path, 0u64..20)
+        .prop_map(|(project_id, path, snapshot_id)| (ProjectId::from(project_id), path, SnapshotId::from(snapshot_id)));
+    prop::collection::vec(row, 0..100)
+}
+
+proptest! {
+    #[test]
+    fn deduplicate_snapshots_is_idempotent(rows in rows()) {
+        let once = deduplicate_snapshots(rows);
+        prop_assert_eq!(deduplicate_snapshots(once.clone()), once);
+    }
+
+    #[test]
+    fn deduplicate_snapshots_keeps_distinct_rows(rows in rows()) {
+        // Make every (project, snapshot) pair distinct.
+        let rows: Vec<(ProjectId, String, SnapshotId)> = rows.into_iter().enumerate()
+            .map(|(i, (project_id, path, _))| (project_id, path, SnapshotId::from(i as u64)))
+            .collect();
+        prop_assert_eq!(deduplicate_snapshots(rows.clone()), rows);
+    }
+
+    #[test]
+    fn filter_by_extensions_keeps_only_allowed(rows in rows(), extensions in prop::sample::subsequence(EXTENSIONS.to_vec(), 0..=4)) {
+        let filtered = filter_by_extensions(rows.clone(), Some(&extensions));
+        for (_, path, _) in filtered.iter() {
+            prop_assert!(extensions.iter().any(|extension| path.ends_with(extension)), "{} is not one of {:?}", path, extensions);
+        }
+        let allowed = rows.iter().filter(|(_, path, _)| extensions.iter().any(|extension| path.ends_with(extension))).count();
+        prop_assert_eq!(filtered.len(), allowed);
+    }
+
+    #[test]
+    fn filter_by_extensions_without_extensions_keeps_all(rows in rows()) {
+        prop_assert_eq!(filter_by_extensions(rows.clone(), None), rows);
+    }
+
+    #[test]
+    fn limit_snapshots_keeps_first_paths(rows in rows(), max in 0usize..120) {
+        let limited = limit_snapshots(rows.clone(), Some(max));
+        prop_assert_eq!(limited.len(), rows.len().min(max));
+        let mut paths: Vec<String> = rows.into_iter().map(|(_, path, _)| path).collect();
+        paths.sort();
+        let mut kept: Vec<String> = limited.into_iter().map(|(_, path, _)| path).collect();
+        kept.sort();
+        prop_assert_eq!(kept, paths[..paths.len().min(max)].to_vec());
+    }
+
+    #[test]
+    fn limit_snapshots_without_max_keeps_all(rows in rows()) {
+        prop_assert_eq!(limit_snapshots(rows.clone(), None), rows);
+    }
+
+    #[test]
+    fn extension_counts_add_up_to_rows(rows in rows()) {
+        let counts = extension_counts(&rows);
+        prop_assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), rows.len());
+        prop_assert!(counts.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    }
+
+    #[test]
+    fn percentile_value_is_between_extremes(values in prop::collection::vec(0.0f64..1e6, 1..50), percentile in 0.0f64..=100.0) {
+        let mut values = values;
+        let value = percentile_value(&mut values, percentile).unwrap();
+        prop_assert!(values[0] <= value && value <= values[values.len() - 1]);
+        prop_assert_eq!(percentile_value(&mut values, 0.0), Some(values[0]));
+        prop_assert_eq!(percentile_value(&mut values, 100.0), Some(values[values.len() - 1]));
+    }
+
+    #[test]
+    fn age_brackets_do_not_overlap(days in 0u64..20 * 365) {
+        let age = Duration::from_days(days);
+        let brackets = [AgeBracket::Young, AgeBracket::Mature, AgeBracket::Legacy];
+        prop_assert_eq!(brackets.iter().filter(|bracket| bracket.contains(age)).count(), 1);
+    }
+}
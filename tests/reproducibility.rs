@@ -0,0 +1,43 @@
+// Sampling with the same seed twice must give byte-identical CSVs: any difference comes from
+// non-determinism such as HashMap iteration order leaking into the output.
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use djanco::database::*;
+use djanco::log::*;
+
+use what_constitutes_software_query::{sample_all_cpp, sample_all_csharp, sample_all_go, sample_all_java, sample_all_js,
+                                      sample_all_py, sample_all_scala, sample_all_ts};
+
+type Query = fn(&Database, &Log, &Path) -> Result<(), std::io::Error>;
+
+// Run a query into a fresh directory and return the contents of the sample it wrote.
+fn run(query: Query, database: &Database, name: &str, run: usize) -> Vec<u8> {
+    let output: PathBuf = std::env::temp_dir().join(format!("reproducibility_{}_{}", name, run));
+    let _ = std::fs::remove_dir_all(&output);
+    std::fs::create_dir_all(&output).unwrap();
+    query(database, &Log::new(Verbosity::Warning), &output).unwrap();
+    std::fs::read(output.join("sample_all.csv")).unwrap()
+}
+
+#[test]
+#[ignore = "needs DJANCO_DATASET"]
+fn sample_all_is_reproducible() {
+    let database = common::database();
+    let queries: [(&str, Query); 8] = [
+        ("java", sample_all_java),
+        ("py", sample_all_py),
+        ("js", sample_all_js),
+        ("scala", sample_all_scala),
+        ("cpp", sample_all_cpp),
+        ("ts", sample_all_ts),
+        ("go", sample_all_go),
+        ("csharp", sample_all_csharp),
+    ];
+    for (name, query) in queries.iter() {
+        let first = run(*query, &database, name, 1);
+        let second = run(*query, &database, name, 2);
+        assert!(first == second, "sample_all_{} differs between two runs with the same seed", name);
+    }
+}
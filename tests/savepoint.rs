@@ -0,0 +1,21 @@
+// --savepoint dates, checked against timestamps computed with `date -u -d DATE +%s`.
+use what_constitutes_software_query::{parse_savepoint, SAVEPOINT};
+
+#[test]
+fn default_savepoint_is_december_2020() {
+    assert_eq!(parse_savepoint("2020-12-01").unwrap(), SAVEPOINT);
+}
+
+#[test]
+fn savepoints_match_unix_timestamps() {
+    assert_eq!(parse_savepoint("1970-01-01").unwrap(), 0);
+    assert_eq!(parse_savepoint("2000-02-29").unwrap(), 951782400);
+    assert_eq!(parse_savepoint("2023-07-15").unwrap(), 1689379200);
+}
+
+#[test]
+fn invalid_savepoints_are_rejected() {
+    for date in ["2021-02-29", "2020-13-01", "2020-12", "December 2020", "2020-12-00"].iter() {
+        assert!(parse_savepoint(date).is_err(), "{} was accepted", date);
+    }
+}